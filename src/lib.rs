@@ -0,0 +1,11 @@
+//! Shut down one or more streams (and futures) gracefully via a shared, clonable trigger.
+
+mod take_until;
+mod tripwire;
+mod wrapper;
+
+pub use take_until::{StreamExt, TakeUntil};
+pub use tripwire::{Trigger, Tripwire};
+pub use wrapper::{
+    cancel_on, AbortRegistration, CancelOn, Outcome, Valve, Valved, ValvedFuture, WithOutcome,
+};
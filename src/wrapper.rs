@@ -1,19 +1,166 @@
 use crate::{StreamExt, TakeUntil, Trigger, Tripwire};
 use futures_core::stream::Stream;
+use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
+#[cfg(any(feature = "tokio-timer", feature = "async-io-timer"))]
+use std::time::{Duration, Instant};
+
+#[cfg(any(feature = "tokio-timer", feature = "async-io-timer"))]
+type BoxedTimer = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+#[cfg(feature = "tokio-timer")]
+fn new_timer(deadline: Instant) -> BoxedTimer {
+    Box::pin(tokio::time::sleep_until(tokio::time::Instant::from_std(deadline)))
+}
+
+#[cfg(all(feature = "async-io-timer", not(feature = "tokio-timer")))]
+fn new_timer(deadline: Instant) -> BoxedTimer {
+    Box::pin(async move {
+        async_io::Timer::at(deadline).await;
+    })
+}
+
+/// The cancellation signal backing a [`Valve`]: either the associated [`Trigger`] being closed,
+/// or, for valves made with [`Valve::timeout`]/[`Valve::deadline`], a wall-clock deadline
+/// elapsing first.
+///
+/// This is an implementation detail of `Valve`/`Valved` and is not exposed publicly.
+enum Signal {
+    Tripwire(Tripwire),
+    #[cfg(any(feature = "tokio-timer", feature = "async-io-timer"))]
+    Timed {
+        tripwire: Tripwire,
+        deadline: Instant,
+        timer: Option<BoxedTimer>,
+    },
+}
+
+impl Clone for Signal {
+    fn clone(&self) -> Self {
+        match self {
+            Signal::Tripwire(tripwire) => Signal::Tripwire(tripwire.clone()),
+            #[cfg(any(feature = "tokio-timer", feature = "async-io-timer"))]
+            Signal::Timed {
+                tripwire,
+                deadline, ..
+            } => Signal::Timed {
+                tripwire: tripwire.clone(),
+                deadline: *deadline,
+                timer: None,
+            },
+        }
+    }
+}
+
+impl std::fmt::Debug for Signal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Signal::Tripwire(tripwire) => f.debug_tuple("Tripwire").field(tripwire).finish(),
+            #[cfg(any(feature = "tokio-timer", feature = "async-io-timer"))]
+            Signal::Timed {
+                tripwire,
+                deadline, ..
+            } => f
+                .debug_struct("Timed")
+                .field("tripwire", tripwire)
+                .field("deadline", deadline)
+                .finish(),
+        }
+    }
+}
+
+impl Future for Signal {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        match &mut *self {
+            Signal::Tripwire(tripwire) => Pin::new(tripwire).poll(cx),
+            #[cfg(any(feature = "tokio-timer", feature = "async-io-timer"))]
+            Signal::Timed {
+                tripwire,
+                deadline,
+                timer,
+            } => {
+                if Pin::new(tripwire).poll(cx).is_ready() {
+                    return Poll::Ready(());
+                }
+                timer.get_or_insert_with(|| new_timer(*deadline)).as_mut().poll(cx)
+            }
+        }
+    }
+}
+
+/// A future that resolves as soon as either of its two constituent futures resolves, ignoring
+/// both of their outputs. Both futures are kept until the `Race` itself is dropped. Used by [`Valve::wrap_until`] to race this valve's own cancellation
+/// signal against an arbitrary extra future.
+struct Race<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A, B> Future for Race<A, B>
+where
+    A: Future,
+    B: Future,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        // safe since we never move nor leak &mut
+        let this = unsafe { self.get_unchecked_mut() };
+
+        let a = unsafe { Pin::new_unchecked(&mut this.a) };
+        if a.poll(cx).is_ready() {
+            return Poll::Ready(());
+        }
+
+        let b = unsafe { Pin::new_unchecked(&mut this.b) };
+        if b.poll(cx).is_ready() {
+            return Poll::Ready(());
+        }
+
+        Poll::Pending
+    }
+}
 
 /// A `Valve` is associated with a [`Trigger`], and can be used to wrap one or more
 /// asynchronous streams. All streams wrapped by a given `Valve` (or its clones) will be
 /// interrupted when [`Trigger::close`] is called on the valve's associated handle.
 #[derive(Clone, Debug)]
-pub struct Valve(Tripwire);
+pub struct Valve(Signal);
 
 impl Valve {
     /// Make a new `Valve` and an associated [`Trigger`].
     pub fn new() -> (Trigger, Self) {
         let (t, tw) = Tripwire::new();
-        (t, Valve(tw))
+        (t, Valve(Signal::Tripwire(tw)))
+    }
+
+    /// Make a new `Valve` that also closes on its own, `duration` from now.
+    ///
+    /// The returned [`Trigger`] can still be closed manually at any point before then; whichever
+    /// of the two happens first wins.
+    #[cfg(any(feature = "tokio-timer", feature = "async-io-timer"))]
+    pub fn timeout(duration: Duration) -> (Trigger, Self) {
+        Self::deadline(Instant::now() + duration)
+    }
+
+    /// Make a new `Valve` that also closes on its own, at `deadline`.
+    ///
+    /// The returned [`Trigger`] can still be closed manually at any point before then; whichever
+    /// of the two happens first wins.
+    #[cfg(any(feature = "tokio-timer", feature = "async-io-timer"))]
+    pub fn deadline(deadline: Instant) -> (Trigger, Self) {
+        let (t, tw) = Tripwire::new();
+        (
+            t,
+            Valve(Signal::Timed {
+                tripwire: tw,
+                deadline,
+                timer: None,
+            }),
+        )
     }
 
     /// Wrap the given `stream` with this `Valve`.
@@ -26,6 +173,125 @@ impl Valve {
     {
         Valved(stream.take_until(self.0.clone()))
     }
+
+    /// Wrap the given `stream` so that it is cancelled as soon as *either* this valve's own
+    /// [`Trigger`] is closed *or* `fut` resolves, whichever happens first.
+    pub fn wrap_until<S, F>(&self, stream: S, fut: F) -> CancelOn<S, impl Future<Output = ()>>
+    where
+        S: Stream,
+        F: Future,
+    {
+        cancel_on(
+            stream,
+            Race {
+                a: self.0.clone(),
+                b: fut,
+            },
+        )
+    }
+
+    /// Make a cheap, cloneable registration handle for this valve.
+    ///
+    /// See [`AbortRegistration`] for details.
+    pub fn registration(&self) -> AbortRegistration {
+        self.clone()
+    }
+
+    /// Wrap the given `stream` with this `Valve`, reporting whether it ended naturally or was
+    /// cancelled.
+    ///
+    /// See [`WithOutcome`] for details.
+    pub fn wrap_with_outcome<S>(&self, stream: S) -> WithOutcome<S>
+    where
+        S: Stream,
+    {
+        WithOutcome {
+            stream,
+            until: self.0.clone(),
+            done: false,
+        }
+    }
+
+    /// Wrap the given `future` with this `Valve`.
+    ///
+    /// When [`Trigger::close`] is called on the handle associated with this valve, the given
+    /// future will immediately resolve to `None` without being polled again.
+    pub fn wrap_future<F>(&self, future: F) -> ValvedFuture<F>
+    where
+        F: Future,
+    {
+        ValvedFuture {
+            future,
+            until: self.0.clone(),
+            done: false,
+        }
+    }
+}
+
+/// A lightweight, cloneable handle that can be created ahead of a stream's existence and later
+/// upgraded into a [`Valved`] via [`Valve::wrap`].
+///
+/// `Valve` is already just a cheap newtype around its cancellation signal, so cloning one *is*
+/// the lightweight, passable handle described by the futures-rs `AbortRegistration` design —
+/// there's no need for a second type with the same shape. This alias exists so that code
+/// registering streams after the fact (possibly across tasks) can say so in its signatures,
+/// without implying it needs the rest of `Valve`'s API (`timeout`, `deadline`, and so on) to do
+/// it. All handles derived from the same originating [`Trigger`] close together.
+///
+/// If the trigger has already fired by the time a stream is registered, the returned `Valved`
+/// immediately yields `None`.
+pub type AbortRegistration = Valve;
+
+/// An item of a [`WithOutcome`] stream: either an item of its inner stream, or a marker that its
+/// [`Trigger`] fired first. When the inner stream is exhausted instead, the `WithOutcome` stream
+/// yields `None` without a marker.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Outcome<T> {
+    /// The inner stream yielded an item.
+    Item(T),
+    /// The `Trigger` fired before the inner stream produced another item.
+    Cancelled,
+}
+
+/// A stream adapter, made with [`Valve::wrap_with_outcome`] or [`Valved::with_outcome`], that
+/// reports whether it ended naturally or was cancelled instead of silently yielding `None`
+/// either way.
+#[derive(Debug)]
+pub struct WithOutcome<S> {
+    stream: S,
+    until: Signal,
+    done: bool,
+}
+
+impl<S> Stream for WithOutcome<S>
+where
+    S: Stream,
+{
+    type Item = Outcome<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // safe since we never move nor leak &mut
+        let this = unsafe { self.get_unchecked_mut() };
+        if this.done {
+            return Poll::Ready(None);
+        }
+
+        let until = unsafe { Pin::new_unchecked(&mut this.until) };
+        if until.poll(cx).is_ready() {
+            this.done = true;
+            return Poll::Ready(Some(Outcome::Cancelled));
+        }
+
+        let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+        match stream.poll_next(cx) {
+            Poll::Ready(Some(item)) => Poll::Ready(Some(Outcome::Item(item))),
+            Poll::Ready(None) => {
+                this.done = true;
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
 }
 
 /// A `Valved` is wrapper around a `Stream` that enables the stream to be turned off remotely to
@@ -33,7 +299,7 @@ impl Valve {
 /// that `Valved` is also produced; when [`Trigger::close`] is called on that handle, the
 /// wrapped stream will immediately yield `None` to indicate that it has completed.
 #[derive(Clone, Debug)]
-pub struct Valved<S>(TakeUntil<S, Tripwire>);
+pub struct Valved<S>(TakeUntil<S, Signal>);
 
 impl<S> Valved<S> {
     /// Make the given stream cancellable.
@@ -46,6 +312,44 @@ impl<S> Valved<S> {
         let (vh, v) = Valve::new();
         (vh, v.wrap(stream))
     }
+
+    /// Make the given stream cancellable, with a deadline `duration` from now in addition to the
+    /// returned [`Trigger`].
+    ///
+    /// See [`Valve::timeout`].
+    #[cfg(any(feature = "tokio-timer", feature = "async-io-timer"))]
+    pub fn timeout(stream: S, duration: Duration) -> (Trigger, Self)
+    where
+        S: Stream,
+    {
+        let (vh, v) = Valve::timeout(duration);
+        (vh, v.wrap(stream))
+    }
+
+    /// Make the given stream cancellable, with a deadline of `deadline` in addition to the
+    /// returned [`Trigger`].
+    ///
+    /// See [`Valve::deadline`].
+    #[cfg(any(feature = "tokio-timer", feature = "async-io-timer"))]
+    pub fn deadline(stream: S, deadline: Instant) -> (Trigger, Self)
+    where
+        S: Stream,
+    {
+        let (vh, v) = Valve::deadline(deadline);
+        (vh, v.wrap(stream))
+    }
+
+    /// Make the given stream cancellable, reporting whether it ended naturally or was cancelled
+    /// instead of silently yielding `None` either way.
+    ///
+    /// See [`WithOutcome`] for details.
+    pub fn with_outcome(stream: S) -> (Trigger, WithOutcome<S>)
+    where
+        S: Stream,
+    {
+        let (vh, v) = Valve::new();
+        (vh, v.wrap_with_outcome(stream))
+    }
 }
 
 impl<S> Stream for Valved<S>
@@ -61,10 +365,114 @@ where
     }
 }
 
+/// A stream that is cancelled as soon as an arbitrary future resolves, rather than being tied to
+/// a [`Trigger`]/[`Tripwire`] pair.
+///
+/// This generalizes the mechanism behind [`Valved`]: `F` can be a `oneshot::Receiver`, a signal
+/// handler future, the completion of another stream, or indeed a [`Tripwire`] itself.
+#[derive(Clone, Debug)]
+pub struct CancelOn<S, F>(TakeUntil<S, F>);
+
+impl<S, F> CancelOn<S, F>
+where
+    S: Stream,
+    F: Future,
+{
+    /// Make the given `stream` cancellable by `fut`.
+    ///
+    /// The returned stream yields items from `stream` until `fut` resolves, at which point it
+    /// immediately yields `None`.
+    pub fn new(stream: S, fut: F) -> Self {
+        CancelOn(stream.take_until(fut))
+    }
+}
+
+impl<S, F> Stream for CancelOn<S, F>
+where
+    S: Stream,
+    F: Future,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // safe since we never move nor leak &mut
+        let inner = unsafe { self.map_unchecked_mut(|s| &mut s.0) };
+        inner.poll_next(cx)
+    }
+}
+
+/// Wrap `stream` so that it is cancelled as soon as `fut` resolves.
+///
+/// This is the free-function form of [`CancelOn::new`]; see there for details.
+pub fn cancel_on<S, F>(stream: S, fut: F) -> CancelOn<S, F>
+where
+    S: Stream,
+    F: Future,
+{
+    CancelOn::new(stream, fut)
+}
+
+/// A `Future` wrapper, made with [`Valve::wrap_future`] or [`ValvedFuture::new`], that shares the
+/// same `Trigger`/`Valve` machinery as [`Valved`] so a single `Trigger` can shut down both
+/// long-lived streams and in-flight futures together.
+///
+/// When the associated [`Trigger`] is closed, the wrapped future resolves to `None` without
+/// being polled again; otherwise it resolves to `Some` of the future's own output.
+#[derive(Debug)]
+pub struct ValvedFuture<F> {
+    future: F,
+    until: Signal,
+    done: bool,
+}
+
+impl<F> ValvedFuture<F>
+where
+    F: Future,
+{
+    /// Make the given future cancellable.
+    ///
+    /// To cancel the future, call [`Trigger::close`] on the returned handle.
+    pub fn new(future: F) -> (Trigger, Self) {
+        let (vh, v) = Valve::new();
+        (vh, v.wrap_future(future))
+    }
+}
+
+impl<F> Future for ValvedFuture<F>
+where
+    F: Future,
+{
+    type Output = Option<F::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // safe since we never move nor leak &mut
+        let this = unsafe { self.get_unchecked_mut() };
+        if this.done {
+            return Poll::Ready(None);
+        }
+
+        let until = unsafe { Pin::new_unchecked(&mut this.until) };
+        if until.poll(cx).is_ready() {
+            this.done = true;
+            return Poll::Ready(None);
+        }
+
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+        match future.poll(cx) {
+            Poll::Ready(output) => {
+                this.done = true;
+                Poll::Ready(Some(output))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use futures_util::stream::empty;
+
     #[test]
     fn valved_stream_may_be_dropped_safely() {
         let _orphan = {
@@ -74,4 +482,80 @@ mod tests {
             trigger
         };
     }
+
+    #[cfg(any(feature = "tokio-timer", feature = "async-io-timer"))]
+    #[test]
+    fn timed_valved_stream_may_be_dropped_safely() {
+        let _orphan = {
+            let s = empty::<()>();
+            let (trigger, wrapped) = Valved::timeout(s, Duration::from_secs(30));
+            let _wrapped = wrapped;
+            trigger
+        };
+    }
+
+    #[test]
+    fn cancel_on_arbitrary_future_may_be_dropped_safely() {
+        use futures_util::future::pending;
+
+        let s = empty::<()>();
+        let _wrapped = cancel_on(s, pending::<()>());
+    }
+
+    #[test]
+    fn wrap_until_is_also_cancelled_by_the_valves_own_trigger() {
+        use futures_util::future::{pending, FutureExt as _};
+        use futures_util::stream::{pending as stream_pending, StreamExt as _};
+
+        let (trigger, valve) = Valve::new();
+        let mut s = valve.wrap_until(stream_pending::<()>(), pending::<()>());
+        trigger.close();
+
+        assert_eq!(s.next().now_or_never(), Some(None));
+    }
+
+    #[test]
+    fn registration_after_close_is_already_terminated() {
+        use futures_util::future::FutureExt as _;
+        use futures_util::stream::{pending, StreamExt as _};
+
+        let (trigger, valve) = Valve::new();
+        let registration = valve.registration();
+        trigger.close();
+
+        // `pending` never yields on its own, so `Some(None)` here can only come from the
+        // registration's trigger having already fired.
+        let mut s = registration.wrap(pending::<()>());
+        assert_eq!(s.next().now_or_never(), Some(None));
+    }
+
+    #[test]
+    fn with_outcome_reports_cancellation() {
+        use futures_util::future::FutureExt as _;
+        use futures_util::stream::{pending, StreamExt as _};
+
+        let (trigger, mut s) = Valved::with_outcome(pending::<()>());
+        assert_eq!(s.next().now_or_never(), None);
+
+        trigger.close();
+        assert_eq!(s.next().now_or_never(), Some(Some(Outcome::Cancelled)));
+    }
+
+    #[test]
+    fn with_outcome_reports_natural_completion() {
+        use futures_util::future::FutureExt as _;
+        use futures_util::stream::StreamExt as _;
+
+        let (_trigger, mut s) = Valved::with_outcome(empty::<()>());
+        assert_eq!(s.next().now_or_never(), Some(None));
+    }
+
+    #[test]
+    fn valved_future_resolves_to_none_on_close() {
+        use futures_util::future::{pending, FutureExt as _};
+
+        let (trigger, f) = ValvedFuture::new(pending::<()>());
+        trigger.close();
+        assert_eq!(f.now_or_never(), Some(None));
+    }
 }